@@ -0,0 +1,93 @@
+#[macro_use]
+extern crate assert_all_eq;
+extern crate criterion;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+// Compares the unrolled expansion of `assert_all_eq!` against the alternative that was
+// considered: bind every expression up front and find the first differing index in a
+// loop over the references. The alternative only works when all expressions have the
+// same type, and it was not faster:
+//
+//     4 u64/unrolled     1.16 ns
+//     4 u64/array loop   1.23 ns
+//     32 u64/unrolled   11.5 ns
+//     32 u64/array loop 14.9 ns
+//
+// With optimizations the index counter is constant folded and every comparison is a
+// single branch to the cold panic path, so the unrolled expansion is kept.
+//
+// The loop is offered as `assert_all_eq_array!` instead, which compiles faster for long
+// lists of arguments. A debug build of 100 invocations with 250 arguments each took 19s
+// with `assert_all_eq!` and 3.5s with `assert_all_eq_array!`.
+macro_rules! array_loop {
+    ( $first:expr , $( $x:expr ),+ ) => ({
+        let a = &$first;
+        let rest = [$( &$x ),+];
+        let mut i = 0;
+        while i < rest.len() {
+            if *a != *rest[i] {
+                panic!("equality assertion failed at position 0 and {}", i + 1);
+            }
+            i += 1;
+        }
+    });
+}
+
+fn small(c: &mut Criterion) {
+    let mut group = c.benchmark_group("4 u64");
+    let v = [7u64; 4];
+    group.bench_function("unrolled", |b| {
+        b.iter(|| {
+            let v = black_box(v);
+            assert_all_eq!(v[0], v[1], v[2], v[3]);
+        })
+    });
+    group.bench_function("array loop", |b| {
+        b.iter(|| {
+            let v = black_box(v);
+            array_loop!(v[0], v[1], v[2], v[3]);
+        })
+    });
+    group.finish();
+}
+
+fn large(c: &mut Criterion) {
+    let mut group = c.benchmark_group("32 u64");
+    let v = [7u64; 32];
+    group.bench_function("unrolled", |b| {
+        b.iter(|| {
+            let v = black_box(v);
+            assert_all_eq!(
+                v[0], v[1], v[2], v[3], v[4], v[5], v[6], v[7], v[8], v[9], v[10], v[11], v[12],
+                v[13], v[14], v[15], v[16], v[17], v[18], v[19], v[20], v[21], v[22], v[23], v[24],
+                v[25], v[26], v[27], v[28], v[29], v[30], v[31]
+            );
+        })
+    });
+    group.bench_function("array loop", |b| {
+        b.iter(|| {
+            let v = black_box(v);
+            array_loop!(
+                v[0], v[1], v[2], v[3], v[4], v[5], v[6], v[7], v[8], v[9], v[10], v[11], v[12],
+                v[13], v[14], v[15], v[16], v[17], v[18], v[19], v[20], v[21], v[22], v[23], v[24],
+                v[25], v[26], v[27], v[28], v[29], v[30], v[31]
+            );
+        })
+    });
+    group.bench_function("array macro", |b| {
+        b.iter(|| {
+            let v = black_box(v);
+            assert_all_eq_array!(
+                v[0], v[1], v[2], v[3], v[4], v[5], v[6], v[7], v[8], v[9], v[10], v[11], v[12],
+                v[13], v[14], v[15], v[16], v[17], v[18], v[19], v[20], v[21], v[22], v[23], v[24],
+                v[25], v[26], v[27], v[28], v[29], v[30], v[31]
+            );
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, small, large);
+criterion_main!(benches);